@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Mul;
+use blstrs::{G1Affine, G1Projective};
+use ff::Field;
+use group::prime::PrimeCurveAffine;
+use group::{Curve, Group};
+use serde::{Deserialize, Serialize};
+
+use crate::nizk_dleq::{prove_gen, verify_proof, DLEqInstance, DLEqWitness, ZkProofDLEq, ZkProofDLEqError};
+use crate::Fr;
+
+/// A single share-holder's verifiable decryption share for an ElGamal-style ciphertext
+/// component `u`: `share = u^x`, accompanied by a DLEQ proof that the same secret `x` underlies
+/// both `share` and the share-holder's public key. The public key itself is not carried here —
+/// it must come from a trusted roster, not from the share-holder.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    pub index: usize,
+    pub share: G1Affine,
+    pub proof: ZkProofDLEq,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecryptionError {
+    NoValidShares,
+    InsufficientShares { needed: usize, got: usize },
+    DuplicateIndex(usize),
+}
+
+/// Produce a verifiable decryption share `u^x` for ciphertext component `u`, using this
+/// share-holder's secret key share `secret` (whose public key is `g^secret`).
+pub fn prove_decryption_share(index: usize, secret: &Fr, u: &G1Affine) -> DecryptionShare {
+    let g = G1Affine::generator();
+    let pubkey = g.mul(secret).to_affine();
+    let share = u.mul(secret).to_affine();
+
+    let k = Fr::random(&mut rand::thread_rng());
+    let instance = DLEqInstance {
+        g,
+        h: *u,
+        g_x: pubkey,
+        h_x: share,
+    };
+    let witness = DLEqWitness {
+        scalar_x: *secret,
+        scalar_r: k,
+    };
+
+    let proof = prove_gen(&instance, &witness);
+    DecryptionShare { index, share, proof }
+}
+
+/// Verify that `decryption_share` was computed as `u^x` for the secret behind `pubkey = g^x`.
+/// `pubkey` must come from the caller's trusted roster/DKG output, not from the share itself —
+/// otherwise a party with no real key share could fabricate a self-consistent `(pubkey, share)`
+/// pair and pass verification.
+pub fn verify_decryption_share(
+    pubkey: &G1Affine,
+    u: &G1Affine,
+    decryption_share: &DecryptionShare,
+) -> Result<(), ZkProofDLEqError> {
+    let g = G1Affine::generator();
+    let instance = DLEqInstance {
+        g,
+        h: *u,
+        g_x: *pubkey,
+        h_x: decryption_share.share,
+    };
+    verify_proof(&instance, &decryption_share.proof)
+}
+
+/// Lagrange coefficient `L_i(0)` for interpolating the polynomial value at `0` from the points
+/// `1, 2, ...` (the usual threshold-scheme convention); the 0-based share index `i` in `indices`
+/// stands for the point `i + 1`.
+fn lagrange_coefficient(indices: &[usize], i: usize) -> Fr {
+    let xi = Fr::from(i as u64 + 1);
+    let mut num = Fr::ONE;
+    let mut den = Fr::ONE;
+    for &j in indices {
+        if j == i {
+            continue;
+        }
+        let xj = Fr::from(j as u64 + 1);
+        num = num * xj;
+        den = den * (xj - xi);
+    }
+    num * den.invert().unwrap()
+}
+
+/// Verify each decryption share against its registered public key in `roster`, discard the ones
+/// that fail (including any share for an index missing from `roster`), and Lagrange-combine the
+/// remainder into the full `u^x` point. Rejects with [`DecryptionError::NoValidShares`] if no
+/// share verifies, with [`DecryptionError::InsufficientShares`] if at least one but fewer than
+/// `threshold` shares verify, and with [`DecryptionError::DuplicateIndex`] if two
+/// verified shares claim the same index.
+pub fn combine_decryption_shares(
+    u: &G1Affine,
+    roster: &BTreeMap<usize, G1Affine>,
+    shares: &[DecryptionShare],
+    threshold: usize,
+) -> Result<G1Affine, DecryptionError> {
+    let mut seen_indices = BTreeSet::new();
+    let mut verified: Vec<&DecryptionShare> = Vec::new();
+    for decryption_share in shares {
+        let Some(pubkey) = roster.get(&decryption_share.index) else {
+            continue;
+        };
+        if verify_decryption_share(pubkey, u, decryption_share).is_err() {
+            continue;
+        }
+        if !seen_indices.insert(decryption_share.index) {
+            return Err(DecryptionError::DuplicateIndex(decryption_share.index));
+        }
+        verified.push(decryption_share);
+    }
+
+    if verified.is_empty() {
+        return Err(DecryptionError::NoValidShares);
+    }
+    if verified.len() < threshold {
+        return Err(DecryptionError::InsufficientShares {
+            needed: threshold,
+            got: verified.len(),
+        });
+    }
+
+    let indices: Vec<usize> = verified.iter().map(|decryption_share| decryption_share.index).collect();
+    let mut combined = G1Projective::identity();
+    for decryption_share in &verified {
+        let lambda = lagrange_coefficient(&indices, decryption_share.index);
+        combined = combined + decryption_share.share.mul(&lambda);
+    }
+    Ok(combined.to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roster_of(entries: &[(usize, G1Affine)]) -> BTreeMap<usize, G1Affine> {
+        entries.iter().copied().collect()
+    }
+
+    #[test]
+    fn round_trip_reconstructs_the_secret_share() {
+        let secret = Fr::random(&mut rand::thread_rng());
+        let u = G1Affine::generator().mul(&Fr::from(11u64)).to_affine();
+        let pubkey = G1Affine::generator().mul(&secret).to_affine();
+
+        let decryption_share = prove_decryption_share(0, &secret, &u);
+        assert!(verify_decryption_share(&pubkey, &u, &decryption_share).is_ok());
+
+        let roster = roster_of(&[(0, pubkey)]);
+        let combined = combine_decryption_shares(&u, &roster, &[decryption_share], 1).unwrap();
+        assert_eq!(combined, u.mul(&secret).to_affine());
+    }
+
+    #[test]
+    fn forged_share_with_a_self_reported_pubkey_is_rejected() {
+        // A party with no real key share picks a fresh secret, computes its own `(pubkey, share)`,
+        // and tries to pass it off under someone else's roster index.
+        let forged_secret = Fr::random(&mut rand::thread_rng());
+        let u = G1Affine::generator().mul(&Fr::from(11u64)).to_affine();
+        let forged_share = prove_decryption_share(0, &forged_secret, &u);
+
+        let real_pubkey = G1Affine::generator().mul(&Fr::random(&mut rand::thread_rng())).to_affine();
+        let roster = roster_of(&[(0, real_pubkey)]);
+
+        assert_eq!(
+            combine_decryption_shares(&u, &roster, &[forged_share], 1),
+            Err(DecryptionError::NoValidShares)
+        );
+    }
+
+    #[test]
+    fn tampered_share_is_rejected() {
+        let secret = Fr::random(&mut rand::thread_rng());
+        let u = G1Affine::generator().mul(&Fr::from(11u64)).to_affine();
+        let pubkey = G1Affine::generator().mul(&secret).to_affine();
+
+        let mut decryption_share = prove_decryption_share(0, &secret, &u);
+        decryption_share.share = G1Affine::generator();
+
+        assert!(verify_decryption_share(&pubkey, &u, &decryption_share).is_err());
+
+        let roster = roster_of(&[(0, pubkey)]);
+        assert_eq!(
+            combine_decryption_shares(&u, &roster, &[decryption_share], 1),
+            Err(DecryptionError::NoValidShares)
+        );
+    }
+
+    #[test]
+    fn below_threshold_is_rejected() {
+        let secret = Fr::random(&mut rand::thread_rng());
+        let u = G1Affine::generator().mul(&Fr::from(11u64)).to_affine();
+        let pubkey = G1Affine::generator().mul(&secret).to_affine();
+
+        let decryption_share = prove_decryption_share(0, &secret, &u);
+        let roster = roster_of(&[(0, pubkey)]);
+
+        assert_eq!(
+            combine_decryption_shares(&u, &roster, &[decryption_share], 2),
+            Err(DecryptionError::InsufficientShares { needed: 2, got: 1 })
+        );
+    }
+
+    #[test]
+    fn duplicate_index_is_rejected() {
+        let secret = Fr::random(&mut rand::thread_rng());
+        let u = G1Affine::generator().mul(&Fr::from(11u64)).to_affine();
+        let pubkey = G1Affine::generator().mul(&secret).to_affine();
+
+        let share_a = prove_decryption_share(0, &secret, &u);
+        let share_b = prove_decryption_share(0, &secret, &u);
+        let roster = roster_of(&[(0, pubkey)]);
+
+        assert_eq!(
+            combine_decryption_shares(&u, &roster, &[share_a, share_b], 1),
+            Err(DecryptionError::DuplicateIndex(0))
+        );
+    }
+}
@@ -1,12 +1,12 @@
+use std::collections::BTreeMap;
 use std::ops::{Mul, Sub};
-use blstrs::G1Affine;
+use blstrs::{G1Affine, G1Projective};
 use ff::Field;
-use group::{Curve, GroupEncoding};
-use rand::rngs::StdRng;
-use rand::SeedableRng;
+use group::{Curve, Group, GroupEncoding};
 use serde::{Deserialize, Serialize};
+use crate::relation::{self, Assignment, Relation, Witness as RelationWitness};
+use crate::transcript::Transcript;
 use crate::Fr;
-use crate::util::sha3_256;
 
 const DOMAIN_PROOF_OF_DLEQ_CHALLENGE: &str = "blsttc-zk-proof-of-dleq-challenge";
 
@@ -40,42 +40,75 @@ pub enum ZkProofDLEqError {
     InvalidInstance,
 }
 
+/// Recompute `c = oracle(g,g^x,h,h^x,g^k,h^k)` for the announcement-based path below; used by
+/// [`prove_gen_announce`] and [`verify_batch`], which need the raw announcements rather than the
+/// generic [`relation::Proof`] shape `prove_gen`/`verify_proof` now go through.
 fn dleq_proof_challenge(g: &G1Affine, g_x: &G1Affine, h: &G1Affine, h_x: &G1Affine, g_k: &G1Affine, h_k: &G1Affine) -> Fr {
-    let mut map = Vec::new();
-    let g_bytes = g.to_bytes();
-    let g_x_bytes = g_x.to_bytes();
-    let h_bytes = h.to_bytes();
-    let h_x_bytes = h_x.to_bytes();
-    let g_k_bytes = g_k.to_bytes();
-    let h_k_bytes = h_k.to_bytes();
-
-    map.append(&mut "g-value".as_bytes().to_vec());
-    map.append(&mut g_bytes.as_ref().to_vec());
-    map.append(&mut "g_x".as_bytes().to_vec());
-    map.append(&mut g_x_bytes.as_ref().to_vec());
-    map.append(&mut "h-value".as_bytes().to_vec());
-    map.append(&mut h_bytes.as_ref().to_vec());
-    map.append(&mut "h_x".as_bytes().to_vec());
-    map.append(&mut h_x_bytes.as_ref().to_vec());
-    map.append(&mut "g_k".as_bytes().to_vec());
-    map.append(&mut g_k_bytes.as_ref().to_vec());
-    map.append(&mut "h_k".as_bytes().to_vec());
-    map.append(&mut h_k_bytes.as_ref().to_vec());
-    map.append(&mut DOMAIN_PROOF_OF_DLEQ_CHALLENGE.as_bytes().to_vec());
-
-    let seed = sha3_256(&map);
-    let mut rng = StdRng::from_seed(seed);
-    let big = Fr::random(&mut rng);
-    return big;
+    let mut transcript = Transcript::new(DOMAIN_PROOF_OF_DLEQ_CHALLENGE);
+    transcript.append_message("g-value", g.to_bytes().as_ref());
+    transcript.append_message("g_x", g_x.to_bytes().as_ref());
+    transcript.append_message("h-value", h.to_bytes().as_ref());
+    transcript.append_message("h_x", h_x.to_bytes().as_ref());
+    transcript.append_message("g_k", g_k.to_bytes().as_ref());
+    transcript.append_message("h_k", h_k.to_bytes().as_ref());
+    transcript.challenge_scalar("challenge")
 }
 
+/// The `dleq(x): g_x = g^x, h_x = h^x` relation, compiled once for `prove_gen`/`verify_proof`.
+fn dleq_relation() -> Relation {
+    crate::define_proof! { dleq(x): g_x = g^x, h_x = h^x }
+}
+
+fn dleq_assignment(instance: &DLEqInstance) -> Assignment {
+    let mut assignment = Assignment::new();
+    assignment.insert("g", instance.g);
+    assignment.insert("h", instance.h);
+    assignment.insert("g_x", instance.g_x);
+    assignment.insert("h_x", instance.h_x);
+    assignment
+}
+
+/// `DLEqInstance`/`ZkProofDLEq` are an instantiation of the generic linear-relation engine in
+/// [`crate::relation`]; this just adapts between their fixed `(c, s)` wire format and the
+/// engine's named `Assignment`/`Witness`/`Proof`.
 pub fn prove_gen(instance: &DLEqInstance, witness: &DLEqWitness) -> ZkProofDLEq {
+    let assignment = dleq_assignment(instance);
+
+    let mut relation_witness = RelationWitness::new();
+    relation_witness.insert("x", witness.scalar_x);
+    let mut nonces = RelationWitness::new();
+    nonces.insert("x", witness.scalar_r);
+
+    let proof = relation::prove_with_nonces(&dleq_relation(), &assignment, &relation_witness, &nonces);
+    ZkProofDLEq {
+        c: proof.c,
+        s: proof.responses["x"],
+    }
+}
+
+pub fn verify_proof(instance: &DLEqInstance, nizk: &ZkProofDLEq) -> Result<(), ZkProofDLEqError> {
+    let assignment = dleq_assignment(instance);
+    let mut responses = BTreeMap::new();
+    responses.insert("x".to_string(), nizk.s);
+    let proof = relation::Proof { c: nizk.c, responses };
+
+    relation::verify(&dleq_relation(), &assignment, &proof).map_err(|_| ZkProofDLEqError::InvalidProof)
+}
 
+/// Announcement-based DLEQ proof encoding, used by [`verify_batch`] for aggregated verification.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZkProofAnnounce {
+    pub g_k: G1Affine,
+    pub h_k: G1Affine,
+    pub s: Fr,
+}
+
+/// Like [`prove_gen`], but returns the announcement-based encoding used for batch verification.
+pub fn prove_gen_announce(instance: &DLEqInstance, witness: &DLEqWitness) -> ZkProofAnnounce {
     let k = witness.scalar_r;
     let g_k = instance.g.mul(&k).to_affine();
     let h_k = instance.h.mul(&k).to_affine();
 
-    // challenge: c = oracle(g,g^x,h,h^x,g^k,h^k)
     let c = dleq_proof_challenge(
         &instance.g,
         &instance.g_x,
@@ -86,32 +119,123 @@ pub fn prove_gen(instance: &DLEqInstance, witness: &DLEqWitness) -> ZkProofDLEq
     );
 
     let s = k.sub(&c.mul(&witness.scalar_x));
-    ZkProofDLEq { c, s }
+    ZkProofAnnounce { g_k, h_k, s }
 }
 
-pub fn verify_proof(instance: &DLEqInstance, nizk: &ZkProofDLEq) -> Result<(), ZkProofDLEqError> {
-
-    let mut g_k_prime = instance.g.mul(&nizk.s).to_affine();
-    g_k_prime = G1Affine::from(g_k_prime + instance.g_x.mul(&nizk.c));
-
-    let mut h_k_prime = instance.h.mul(&nizk.s).to_affine();
-    h_k_prime = G1Affine::from(h_k_prime + instance.h_x.mul( &nizk.c)
-    );
+/// Verify a batch of DLEQ proofs via two aggregated multi-scalar multiplications, weighted by
+/// random `rho_i`, instead of `2n` independent equation checks.
+pub fn verify_batch(
+    instances: &[DLEqInstance],
+    proofs: &[ZkProofAnnounce],
+) -> Result<(), ZkProofDLEqError> {
+    if instances.is_empty() || instances.len() != proofs.len() {
+        return Err(ZkProofDLEqError::InvalidInstance);
+    }
 
-    // Verifier's challenge
-    // c' = oracle(g,g^x,h,h^x,g^k',h^k')
-    let c_prime = dleq_proof_challenge(
-        &instance.g,
-        &instance.g_x,
-        &instance.h,
-        &instance.h_x,
-        &g_k_prime,
-        &h_k_prime,
-    );
+    let mut rng = rand::thread_rng();
+    let mut g_acc = G1Projective::identity();
+    let mut h_acc = G1Projective::identity();
+
+    for (instance, proof) in instances.iter().zip(proofs.iter()) {
+        let c = dleq_proof_challenge(
+            &instance.g,
+            &instance.g_x,
+            &instance.h,
+            &instance.h_x,
+            &proof.g_k,
+            &proof.h_k,
+        );
+        let rho = Fr::random(&mut rng);
+
+        let g_term = instance.g.mul(&proof.s) + instance.g_x.mul(&c) - G1Projective::from(proof.g_k);
+        let h_term = instance.h.mul(&proof.s) + instance.h_x.mul(&c) - G1Projective::from(proof.h_k);
+
+        g_acc = g_acc + g_term.mul(&rho);
+        h_acc = h_acc + h_term.mul(&rho);
+    }
 
-    if nizk.c == c_prime {
+    if bool::from(g_acc.is_identity()) && bool::from(h_acc.is_identity()) {
         Ok(())
     } else {
-        return Err(ZkProofDLEqError::InvalidProof);
+        Err(ZkProofDLEqError::InvalidProof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use group::prime::PrimeCurveAffine;
+
+    fn sharing_instance(seed: u64) -> (DLEqInstance, DLEqWitness) {
+        let g = G1Affine::generator();
+        let h = g.mul(&Fr::from(7u64)).to_affine();
+        let scalar_x = Fr::from(seed);
+        let scalar_r = Fr::random(&mut rand::thread_rng());
+
+        let g_x = g.mul(&scalar_x).to_affine();
+        let h_x = h.mul(&scalar_x).to_affine();
+
+        (
+            DLEqInstance { g, h, g_x, h_x },
+            DLEqWitness { scalar_x, scalar_r },
+        )
+    }
+
+    #[test]
+    fn single_proof_round_trip() {
+        let (instance, witness) = sharing_instance(42);
+        let proof = prove_gen(&instance, &witness);
+        assert!(verify_proof(&instance, &proof).is_ok());
+    }
+
+    #[test]
+    fn single_proof_tampered_is_rejected() {
+        let (instance, witness) = sharing_instance(42);
+        let mut proof = prove_gen(&instance, &witness);
+        proof.s += Fr::ONE;
+        assert_eq!(verify_proof(&instance, &proof), Err(ZkProofDLEqError::InvalidProof));
+    }
+
+    #[test]
+    fn batch_round_trip() {
+        let (instance_a, witness_a) = sharing_instance(1);
+        let (instance_b, witness_b) = sharing_instance(2);
+        let (instance_c, witness_c) = sharing_instance(3);
+
+        let instances = vec![instance_a, instance_b, instance_c];
+        let proofs = vec![
+            prove_gen_announce(&instances[0], &witness_a),
+            prove_gen_announce(&instances[1], &witness_b),
+            prove_gen_announce(&instances[2], &witness_c),
+        ];
+
+        assert!(verify_batch(&instances, &proofs).is_ok());
+    }
+
+    #[test]
+    fn batch_rejects_a_single_tampered_proof() {
+        let (instance_a, witness_a) = sharing_instance(1);
+        let (instance_b, witness_b) = sharing_instance(2);
+
+        let instances = vec![instance_a, instance_b];
+        let mut proofs = vec![
+            prove_gen_announce(&instances[0], &witness_a),
+            prove_gen_announce(&instances[1], &witness_b),
+        ];
+        proofs[1].s += Fr::ONE;
+
+        assert_eq!(verify_batch(&instances, &proofs), Err(ZkProofDLEqError::InvalidProof));
+    }
+
+    #[test]
+    fn batch_rejects_mismatched_lengths() {
+        let (instance_a, witness_a) = sharing_instance(1);
+        let proofs = vec![prove_gen_announce(&instance_a, &witness_a)];
+
+        assert_eq!(
+            verify_batch(&[instance_a], &[]),
+            Err(ZkProofDLEqError::InvalidInstance)
+        );
+        assert_eq!(verify_batch(&[], &proofs), Err(ZkProofDLEqError::InvalidInstance));
     }
 }
\ No newline at end of file
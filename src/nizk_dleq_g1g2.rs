@@ -0,0 +1,146 @@
+use std::ops::{Mul, Sub};
+use blstrs::{G1Affine, G2Affine};
+use ff::Field;
+use group::{Curve, GroupEncoding};
+use serde::{Deserialize, Serialize};
+use crate::nizk_dleq::ZkProofDLEqError;
+use crate::transcript::Transcript;
+use crate::Fr;
+
+const DOMAIN_PROOF_OF_DLEQ_G1G2_CHALLENGE: &str = "blsttc-zk-proof-of-dleq-g1g2-challenge";
+
+///   instance = (g1,g2,g1^x,g2^x)
+///   g1 and g2 are generators of G1 and G2 respectively; proves that the same secret scalar `x`
+///   underlies both `g1_x` and `g2_x`, e.g. that a G1 public key and its G2 counterpart agree.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DLEqInstanceG1G2 {
+    pub g1: G1Affine,
+    pub g2: G2Affine,
+    pub g1_x: G1Affine,
+    pub g2_x: G2Affine,
+}
+
+/// Witness for the validity of a cross-group sharing instance.
+///   Witness = x
+pub struct DLEqWitnessG1G2 {
+    pub scalar_x: Fr,
+}
+
+/// Zero-knowledge proof that a G1 element and a G2 element commit to the same discrete log.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ZkProofDLEqG1G2 {
+    pub c: Fr,
+    pub s: Fr,
+}
+
+/// G1 and G2 share the scalar field `Fr`, so a single challenge (rather than one per group)
+/// suffices to bind both Chaum–Pedersen equations together.
+fn dleq_g1g2_proof_challenge(
+    g1: &G1Affine,
+    g1_x: &G1Affine,
+    g2: &G2Affine,
+    g2_x: &G2Affine,
+    g1_k: &G1Affine,
+    g2_k: &G2Affine,
+) -> Fr {
+    let mut transcript = Transcript::new(DOMAIN_PROOF_OF_DLEQ_G1G2_CHALLENGE);
+    transcript.append_message("g1-value", g1.to_bytes().as_ref());
+    transcript.append_message("g1_x", g1_x.to_bytes().as_ref());
+    transcript.append_message("g2-value", g2.to_bytes().as_ref());
+    transcript.append_message("g2_x", g2_x.to_bytes().as_ref());
+    transcript.append_message("g1_k", g1_k.to_bytes().as_ref());
+    transcript.append_message("g2_k", g2_k.to_bytes().as_ref());
+    transcript.challenge_scalar("challenge")
+}
+
+pub fn prove_gen_g1g2(instance: &DLEqInstanceG1G2, witness: &DLEqWitnessG1G2) -> ZkProofDLEqG1G2 {
+    let k = Fr::random(&mut rand::thread_rng());
+    let g1_k = instance.g1.mul(&k).to_affine();
+    let g2_k = instance.g2.mul(&k).to_affine();
+
+    // challenge: c = oracle(g1,g1^x,g2,g2^x,g1^k,g2^k)
+    let c = dleq_g1g2_proof_challenge(
+        &instance.g1,
+        &instance.g1_x,
+        &instance.g2,
+        &instance.g2_x,
+        &g1_k,
+        &g2_k,
+    );
+
+    let s = k.sub(&c.mul(&witness.scalar_x));
+    ZkProofDLEqG1G2 { c, s }
+}
+
+pub fn verify_proof_g1g2(
+    instance: &DLEqInstanceG1G2,
+    nizk: &ZkProofDLEqG1G2,
+) -> Result<(), ZkProofDLEqError> {
+    let mut g1_k_prime = instance.g1.mul(&nizk.s).to_affine();
+    g1_k_prime = G1Affine::from(g1_k_prime + instance.g1_x.mul(&nizk.c));
+
+    let mut g2_k_prime = instance.g2.mul(&nizk.s).to_affine();
+    g2_k_prime = G2Affine::from(g2_k_prime + instance.g2_x.mul(&nizk.c));
+
+    // Verifier's challenge
+    // c' = oracle(g1,g1^x,g2,g2^x,g1^k',g2^k')
+    let c_prime = dleq_g1g2_proof_challenge(
+        &instance.g1,
+        &instance.g1_x,
+        &instance.g2,
+        &instance.g2_x,
+        &g1_k_prime,
+        &g2_k_prime,
+    );
+
+    if nizk.c == c_prime {
+        Ok(())
+    } else {
+        Err(ZkProofDLEqError::InvalidProof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use group::prime::PrimeCurveAffine;
+
+    fn cross_group_instance() -> (DLEqInstanceG1G2, DLEqWitnessG1G2) {
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let scalar_x = Fr::random(&mut rand::thread_rng());
+
+        let g1_x = g1.mul(&scalar_x).to_affine();
+        let g2_x = g2.mul(&scalar_x).to_affine();
+
+        (
+            DLEqInstanceG1G2 { g1, g2, g1_x, g2_x },
+            DLEqWitnessG1G2 { scalar_x },
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let (instance, witness) = cross_group_instance();
+        let proof = prove_gen_g1g2(&instance, &witness);
+        assert!(verify_proof_g1g2(&instance, &proof).is_ok());
+    }
+
+    #[test]
+    fn tampered_response_is_rejected() {
+        let (instance, witness) = cross_group_instance();
+        let mut proof = prove_gen_g1g2(&instance, &witness);
+        proof.s += Fr::ONE;
+        assert_eq!(verify_proof_g1g2(&instance, &proof), Err(ZkProofDLEqError::InvalidProof));
+    }
+
+    #[test]
+    fn mismatched_secret_is_rejected() {
+        let (instance, _witness) = cross_group_instance();
+        let wrong_witness = DLEqWitnessG1G2 {
+            scalar_x: Fr::random(&mut rand::thread_rng()),
+        };
+        let proof = prove_gen_g1g2(&instance, &wrong_witness);
+        assert_eq!(verify_proof_g1g2(&instance, &proof), Err(ZkProofDLEqError::InvalidProof));
+    }
+}
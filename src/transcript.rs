@@ -0,0 +1,44 @@
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+use crate::Fr;
+
+/// A Merlin-style Fiat–Shamir transcript built on a SHAKE256 sponge.
+///
+/// Messages are absorbed under a label with [`append_message`](Transcript::append_message), and
+/// challenge scalars are squeezed out under a label with
+/// [`challenge_scalar`](Transcript::challenge_scalar). Because the challenge is derived directly
+/// from the sponge state rather than by seeding a general-purpose PRNG, it depends only on the
+/// absorbed bytes and is stable across `rand` crate versions.
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Shake256,
+}
+
+impl Transcript {
+    /// Start a new transcript, domain-separated by `label`.
+    pub fn new(label: &'static str) -> Self {
+        let mut hasher = Shake256::default();
+        hasher.update(label.as_bytes());
+        Transcript { hasher }
+    }
+
+    /// Absorb `message` into the transcript under `label`.
+    pub fn append_message(&mut self, label: &'static str, message: &[u8]) {
+        self.hasher.update(label.as_bytes());
+        self.hasher.update(&(message.len() as u64).to_le_bytes());
+        self.hasher.update(message);
+    }
+
+    /// Squeeze a challenge scalar out of the transcript under `label`.
+    ///
+    /// 64 bytes are drawn from the sponge and reduced mod `r` via wide reduction, so the result
+    /// is statistically close to uniform over `Fr` with no measurable modular bias.
+    pub fn challenge_scalar(&mut self, label: &'static str) -> Fr {
+        self.hasher.update(label.as_bytes());
+        let mut reader = self.hasher.clone().finalize_xof();
+        let mut wide = [0u8; 64];
+        reader.read(&mut wide);
+        Fr::from_bytes_wide(&wide)
+    }
+}